@@ -0,0 +1,41 @@
+//! Benchmarks comparing serial and parallel gate application.
+//!
+//! Run the serial baseline with `cargo bench` and the rayon path with
+//! `cargo bench --features parallel`; each bench applies a full-width gate to a
+//! fresh register, which is a dense `2^width` matrix-vector multiply.
+
+#![feature(test)]
+
+extern crate quantum;
+extern crate test;
+
+use quantum::computer::QuantumComputer;
+use quantum::gate::Gate;
+use quantum::gates;
+
+use test::Bencher;
+
+/// Width at which the matrix-vector multiply dominates the runtime.
+const WIDTH: usize = 10;
+
+#[bench]
+fn apply_hadamard(b: &mut Bencher) {
+    b.iter(|| {
+        let mut c = QuantumComputer::new(WIDTH);
+        c.initialize(0);
+
+        // A Hadamard on every qubit, each a full-width dense apply.
+        for q in 0..WIDTH {
+            c.apply(Gate::on_qubits(WIDTH, &[q], &gates::hadamard(1)));
+        }
+    });
+}
+
+#[bench]
+fn apply_qft(b: &mut Bencher) {
+    b.iter(|| {
+        let mut c = QuantumComputer::new(WIDTH);
+        c.initialize(0);
+        c.apply(gates::qft(WIDTH));
+    });
+}
@@ -4,6 +4,7 @@ use std::f64::consts::PI;
 use std::fmt;
 use std::ops::Add;
 use std::ops::AddAssign;
+use std::ops::Div;
 use std::ops::Mul;
 use std::ops::MulAssign;
 use std::ops::Neg;
@@ -92,6 +93,63 @@ impl Complex {
         }
     }
 
+    /// The complex conjugate, i.e. `re - im * i`.
+    pub fn conj(&self) -> Complex {
+        Complex::new(self.re, -self.im)
+    }
+
+    /// The norm/absolute value/modulus, i.e. `|z|`.
+    pub fn norm(&self) -> f64 {
+        self.norm_sqr().sqrt()
+    }
+
+    /// The argument/phase of this number, in the range `(-PI, PI]`.
+    pub fn arg(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// The multiplicative inverse, i.e. `1 / z = conj(z) / |z|^2`.
+    pub fn inv(&self) -> Complex {
+        let d = self.norm_sqr();
+
+        Complex::new(self.re / d, -self.im / d)
+    }
+
+    /// The complex exponential, `exp(a + bi) = e^a * (cos b + i sin b)`.
+    pub fn exp(&self) -> Complex {
+        Complex::new_euler(self.re.exp(), self.im)
+    }
+
+    /// The principal complex logarithm, `ln z = ln|z| + i * arg z`.
+    pub fn ln(&self) -> Complex {
+        Complex::new(self.norm().ln(), self.arg())
+    }
+
+    /// The principal square root of this number.
+    pub fn sqrt(&self) -> Complex {
+        Complex::new_euler(self.norm().sqrt(), self.arg() / 2f64)
+    }
+
+    /// The complex sine.
+    pub fn sin(&self) -> Complex {
+        Complex::new(self.re.sin() * self.im.cosh(), self.re.cos() * self.im.sinh())
+    }
+
+    /// The complex cosine.
+    pub fn cos(&self) -> Complex {
+        Complex::new(self.re.cos() * self.im.cosh(), -self.re.sin() * self.im.sinh())
+    }
+
+    /// The complex hyperbolic sine.
+    pub fn sinh(&self) -> Complex {
+        Complex::new(self.re.sinh() * self.im.cos(), self.re.cosh() * self.im.sin())
+    }
+
+    /// The complex hyperbolic cosine.
+    pub fn cosh(&self) -> Complex {
+        Complex::new(self.re.cosh() * self.im.cos(), self.re.sinh() * self.im.sin())
+    }
+
     /// The real part.
     pub fn re(&self) -> f64 {
         self.re
@@ -130,6 +188,17 @@ impl Mul<Complex> for Complex {
     }
 }
 
+impl Div<Complex> for Complex {
+    type Output = Complex;
+
+    fn div(self, rhs: Complex) -> Complex {
+        let d = rhs.norm_sqr();
+
+        Complex::new((self.re * rhs.re + self.im * rhs.im) / d,
+                     (self.im * rhs.re - self.re * rhs.im) / d)
+    }
+}
+
 impl AddAssign for Complex {
     fn add_assign(&mut self, rhs: Complex) {
         *self = *self + rhs;
@@ -171,3 +240,29 @@ fn complex_test() {
 
     assert_eq!(Complex::one(), c![7f64, 8f64].pow(0));
 }
+
+#[test]
+fn analytic_test() {
+    let z = c![3f64, 4f64];
+
+    assert_eq!(c![3f64, -4f64], z.conj());
+    assert_eq!(5f64, z.norm());
+
+    // z * z.inv() == 1
+    assert!(Complex::one().approx_eq(&(z * z.inv())));
+
+    // (z / z) == 1
+    assert!(Complex::one().approx_eq(&(z / z)));
+
+    // exp(ln z) == z
+    assert!(z.approx_eq(&z.ln().exp()));
+
+    // sqrt(z)^2 == z
+    assert!(z.approx_eq(&(z.sqrt() * z.sqrt())));
+
+    // sin^2 + cos^2 == 1
+    assert!(Complex::one().approx_eq(&(z.sin() * z.sin() + z.cos() * z.cos())));
+
+    // cosh^2 - sinh^2 == 1
+    assert!(Complex::one().approx_eq(&(z.cosh() * z.cosh() + (-(z.sinh() * z.sinh())))));
+}
@@ -1,8 +1,15 @@
 //! Main consumer module allowing easy control of whole quantum computer.
 
+use std::collections::HashMap;
+
+use complex::Complex;
 use gate::Gate;
+use qasm;
+use qasm::Instruction;
 use registers::ClassicalRegister;
+use registers::MeasurementBasis;
 use registers::QuantumRegister;
+use state_prep;
 
 #[derive(Debug, Eq, PartialEq)]
 enum State {
@@ -31,6 +38,9 @@ pub struct QuantumComputer {
 
     /// Only makes sense if `State::Collapsed == state`
     classical: ClassicalRegister,
+
+    /// Log of the instructions applied, for QASM export.
+    log: Vec<Instruction>,
 }
 
 impl QuantumComputer {
@@ -41,7 +51,46 @@ impl QuantumComputer {
             width: width,
             register: QuantumRegister::new(width, &ClassicalRegister::zeroed(width)),
             classical: ClassicalRegister::zeroed(width),
+            log: Vec::new(),
+        }
+    }
+
+    /// Build a quantum computer from OpenQASM 2.0 source and run it.
+    ///
+    /// The register is sized from the `qreg` declaration, initialized to zero,
+    /// and each parsed instruction is applied in turn (a `measure` collapses
+    /// the register).  The instruction log is populated so the program can be
+    /// exported again with `to_qasm`.
+    pub fn from_qasm(src: &str) -> QuantumComputer {
+        let (width, instructions) = qasm::parse(src);
+
+        let mut computer = QuantumComputer::new(width);
+        computer.initialize(0);
+
+        for inst in instructions {
+            computer.apply_instruction(inst);
+        }
+
+        computer
+    }
+
+    /// Apply a single QASM instruction, expanding it to the register width.
+    ///
+    /// A `Measure` collapses the register; every other instruction is expanded
+    /// with `Gate::on_qubits` and applied.  The instruction is appended to the
+    /// log either way.
+    pub fn apply_instruction(&mut self, inst: Instruction) {
+        match qasm::gate_for(&inst, self.width) {
+            Some(gate) => self.apply(gate),
+            None => self.collapse(),
         }
+
+        self.log.push(inst);
+    }
+
+    /// Serialize the applied instruction log back to OpenQASM 2.0 source.
+    pub fn to_qasm(&self) -> String {
+        qasm::emit(self.width, &self.log)
     }
 
     /// Initialize the quantum register qubits to a certian classical integer state.
@@ -58,6 +107,30 @@ impl QuantumComputer {
         self.state = State::Running;
     }
 
+    /// Initialize the quantum register into an arbitrary amplitude vector.
+    ///
+    /// `amplitudes` must be a normalized ket of length `2^width`.  We compile it
+    /// to a gate sequence with `state_prep::circuit` (Möttönen multiplexed
+    /// rotations) and apply it from the all-zero state, so the register ends in
+    /// the requested superposition up to a global phase.
+    ///
+    /// # Panics
+    ///
+    /// We panic if the state is anything other than `State::Initializing`, or if
+    /// `amplitudes` is not a normalized ket of length `2^width`.
+    pub fn initialize_amplitudes(&mut self, amplitudes: Vec<Complex>) {
+        assert_eq!(State::Initializing, self.state);
+
+        let circuit = state_prep::circuit(&amplitudes, self.width);
+
+        self.register = QuantumRegister::new(self.width, &ClassicalRegister::zeroed(self.width));
+        self.state = State::Running;
+
+        for gate in circuit {
+            self.register.apply(gate);
+        }
+    }
+
     /// Apply a quantum gate to the quantum register qubits.
     ///
     /// # Panics
@@ -82,6 +155,60 @@ impl QuantumComputer {
         self.state = State::Collapsed;
     }
 
+    /// Run the circuit `shots` times and tally the collapsed integer outcomes.
+    ///
+    /// Rather than destroying the superposition with `collapse`, we sample the
+    /// running register's probability distribution `shots` times and return a
+    /// histogram keyed by the observed integer state.  The register is left in
+    /// its current superposition, so this can be called repeatedly, giving the
+    /// empirical output distribution of the circuit in one call.
+    ///
+    /// # Panics
+    ///
+    /// We panic if the state is anything other than `State::Running`.
+    pub fn run_shots(&mut self, shots: usize) -> HashMap<u32, usize> {
+        assert_eq!(State::Running, self.state);
+
+        self.register.measure_shots(shots)
+    }
+
+    /// Collapse the register after measuring in the given basis.
+    ///
+    /// The computational (`Z`) basis collapses directly.  The `X` and `Y` bases
+    /// are read out by first rotating their eigenbasis onto the computational
+    /// one — a Hadamard on each qubit for `X`, a `phase_shift(-PI / 2)` then a
+    /// Hadamard for `Y` — and then collapsing as usual, so the recorded integer
+    /// is the outcome in the requested basis.
+    ///
+    /// # Panics
+    ///
+    /// We panic if the state is anything other than `State::Running`.
+    pub fn collapse_in_basis(&mut self, basis: MeasurementBasis) {
+        use std::f64::consts::PI;
+
+        use gates;
+
+        assert_eq!(State::Running, self.state);
+
+        match basis {
+            MeasurementBasis::Computational => {}
+            MeasurementBasis::X => {
+                for q in 0..self.width {
+                    self.register.apply(Gate::on_qubits(self.width, &[q], &gates::hadamard(1)));
+                }
+            }
+            MeasurementBasis::Y => {
+                for q in 0..self.width {
+                    self.register
+                        .apply(Gate::on_qubits(self.width, &[q], &gates::phase_shift(-PI / 2f64)));
+                    self.register.apply(Gate::on_qubits(self.width, &[q], &gates::hadamard(1)));
+                }
+            }
+        }
+
+        self.collapse();
+    }
+
     /// Reset the quantum register, ready to be initialized again.
     ///
     /// # Panics
@@ -146,6 +273,123 @@ fn compute_test() {
     assert_eq!(5, c.value());
 }
 
+#[test]
+fn qasm_test() {
+    // Flip the single qubit with X, then measure: the result is 1.
+    let src = "OPENQASM 2.0; qreg q[1]; creg c[1]; x q[0]; measure q -> c;";
+
+    let c = QuantumComputer::from_qasm(src);
+
+    assert_eq!(1, c.value());
+
+    // The applied program round-trips back out through the log.
+    let (_, instructions) = qasm::parse(&c.to_qasm());
+    assert_eq!(vec![Instruction::X(0), Instruction::Measure], instructions);
+}
+
+#[test]
+fn initialize_amplitudes_test() {
+    use float_cmp::ApproxEqUlps;
+
+    let s = 2f64.sqrt().recip();
+
+    let mut c = QuantumComputer::new(1);
+    c.initialize_amplitudes(vec![c![s, 0f64], c![s, 0f64]]);
+
+    assert!(0.5f64.approx_eq_ulps(&c.probabilities()[0], 10));
+    assert!(0.5f64.approx_eq_ulps(&c.probabilities()[1], 10));
+
+    // A computational basis state comes through unchanged.
+    let mut c = QuantumComputer::new(2);
+    c.initialize_amplitudes(vec![c![0f64, 0f64], c![0f64, 0f64], c![1f64, 0f64], c![0f64, 0f64]]);
+
+    let p = c.probabilities();
+    assert!(1f64.approx_eq_ulps(&p[2], 10));
+}
+
+#[test]
+fn initialize_amplitudes_phase_test() {
+    use float_cmp::ApproxEqUlps;
+
+    let s = 2f64.sqrt().recip();
+
+    // |+i> = (|0> + i|1>) / sqrt(2) carries a non-trivial relative phase, so it
+    // exercises the R_z phase cascade rather than just the magnitude cascade.
+    let mut c = QuantumComputer::new(1);
+    c.initialize_amplitudes(vec![c![s, 0f64], c![0f64, s]]);
+
+    // The magnitudes are an equal superposition...
+    let p = c.probabilities();
+    assert!(0.5f64.approx_eq_ulps(&p[0], 10));
+    assert!(0.5f64.approx_eq_ulps(&p[1], 10));
+
+    // ...and the phase makes it the +1 eigenstate of Y, so a Y-basis
+    // measurement is deterministic.
+    c.collapse_in_basis(MeasurementBasis::Y);
+    assert_eq!(0, c.value());
+}
+
+#[test]
+fn initialize_amplitudes_entangled_test() {
+    use float_cmp::ApproxEqUlps;
+
+    // A genuinely entangled three-qubit state: earlier coverage was all width-1
+    // or trivial width-2 basis states, which hid a multiplexor bug that only
+    // bites states needing two-or-more-control rotations.
+    let raw = [c![0.1, 0f64],
+               c![0.2, 0f64],
+               c![0.3, 0f64],
+               c![0.4, 0f64],
+               c![0.5, 0f64],
+               c![0.3, 0f64],
+               c![0.4, 0f64],
+               c![0.447, 0f64]];
+
+    let norm = raw.iter().map(|a| a.norm_sqr()).sum::<f64>().sqrt();
+    let amplitudes: Vec<Complex> = raw.iter().map(|a| c![a.re() / norm, 0f64]).collect();
+
+    let mut c = QuantumComputer::new(3);
+    c.initialize_amplitudes(amplitudes.clone());
+
+    // Every outcome probability must match the squared magnitude of the target.
+    let p = c.probabilities();
+    for (i, amp) in amplitudes.iter().enumerate() {
+        assert!(amp.norm_sqr().approx_eq_ulps(&p[i], 64));
+    }
+}
+
+#[test]
+fn collapse_in_basis_test() {
+    use gates;
+
+    // H|0> = |+>, the +1 eigenstate of X, so an X measurement is deterministic.
+    let mut c = QuantumComputer::new(1);
+    c.initialize(0);
+    c.apply(gates::hadamard(1));
+    c.collapse_in_basis(MeasurementBasis::X);
+    assert_eq!(0, c.value());
+}
+
+#[test]
+fn run_shots_test() {
+    use gates;
+
+    let mut c = QuantumComputer::new(1);
+
+    c.initialize(0);
+    c.apply(gates::hadamard(1));
+
+    let histogram = c.run_shots(1000);
+
+    // Both outcomes appear and the counts sum to the number of shots.
+    assert_eq!(1000, histogram.values().sum::<usize>());
+    assert!(histogram.contains_key(&0));
+    assert!(histogram.contains_key(&1));
+
+    // Sampling did not collapse the register; it is still Running.
+    assert_eq!(State::Running, c.state);
+}
+
 #[test]
 fn probabilities_test() {
     use float_cmp::ApproxEqUlps;
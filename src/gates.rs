@@ -1,5 +1,7 @@
 //! Implementations of quantum gates, intended for consumer use.
 
+use std::f64::consts::PI;
+
 use complex::Complex;
 
 use gate::Gate;
@@ -194,6 +196,53 @@ pub fn controlled_z() -> Gate {
     controlled(pauli_z().matrix())
 }
 
+/// A multi-controlled single qubit gate on an arbitrary register layout.
+///
+/// The 2x2 matrix `u` is applied to `target` only on those basis states where
+/// every qubit in `controls` is `|1>`; all other amplitudes are left in place.
+/// Qubit `0` is the most significant bit of the basis index, matching `toffoli`
+/// and `fredkin`.
+///
+/// n-controlled-X, n-controlled-phase, and the Toffoli/Fredkin gates are all
+/// special cases with a configurable qubit layout.
+///
+/// # Panics
+///
+/// We panic if `u` isn't 2x2, if `target` is out of range or appears in
+/// `controls`, or if any control is out of range for `width`.
+pub fn multi_controlled(u: &Matrix, controls: &[usize], target: usize, width: usize) -> Gate {
+    assert_eq!(2, u.size());
+    assert!(target < width);
+    for &c in controls {
+        assert!(c < width);
+        assert!(c != target);
+    }
+
+    // Map qubit indices to basis-index bit positions (qubit 0 is most
+    // significant).
+    let target_bit = 1usize << (width - 1 - target);
+    let control_mask = controls
+        .iter()
+        .fold(0usize, |mask, &c| mask | (1usize << (width - 1 - c)));
+
+    let mut m = Matrix::identity(Ket::size(width));
+
+    // Embed `u` on each index pair differing only in the target bit, where all
+    // control bits are set.
+    for x in 0..Ket::size(width) {
+        if control_mask == (x & control_mask) && 0 == (x & target_bit) {
+            let x1 = x | target_bit;
+
+            m.set(x, x, u.get(0, 0));
+            m.set(x, x1, u.get(0, 1));
+            m.set(x1, x, u.get(1, 0));
+            m.set(x1, x1, u.get(1, 1));
+        }
+    }
+
+    Gate::new(width, m)
+}
+
 /// The three qubit Toffoli gate.
 ///
 /// If the first two bits are in the state |1> , it applies a Pauli-X on the third bit,
@@ -255,6 +304,144 @@ pub fn quantum_fourier_transform(n: usize) -> Gate {
     Gate::new(n, m)
 }
 
+/// A single qubit rotation about the _x_ axis of the Bloch sphere.
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Quantum_logic_gate#Rotation_operator_gates)
+/// for more information.
+pub fn rx(theta: f64) -> Gate {
+    let c = (theta / 2f64).cos();
+    let s = (theta / 2f64).sin();
+
+    let m = m![c![c, 0f64],  c![0f64, -s];
+               c![0f64, -s], c![c, 0f64]];
+
+    Gate::new(1, m)
+}
+
+/// A single qubit rotation about the _y_ axis of the Bloch sphere.
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Quantum_logic_gate#Rotation_operator_gates)
+/// for more information.
+pub fn ry(theta: f64) -> Gate {
+    let c = (theta / 2f64).cos();
+    let s = (theta / 2f64).sin();
+
+    let m = m![c![c, 0f64], c![-s, 0f64];
+               c![s, 0f64], c![c, 0f64]];
+
+    Gate::new(1, m)
+}
+
+/// A single qubit rotation about the _z_ axis of the Bloch sphere.
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Quantum_logic_gate#Rotation_operator_gates)
+/// for more information.
+pub fn rz(theta: f64) -> Gate {
+    let m = m![Complex::new_euler(1f64, -theta / 2f64), Complex::zero();
+               Complex::zero(),                         Complex::new_euler(1f64, theta / 2f64)];
+
+    Gate::new(1, m)
+}
+
+/// A single qubit phase gate applying `e^{i * lambda}` to the `|1>` amplitude.
+///
+/// This builds the same matrix as `phase_shift`; `s` and `t` are the fixed
+/// `PI / 2` and `PI / 4` instances.
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Quantum_logic_gate#Phase_shift_gates)
+/// for more information.
+pub fn phase(lambda: f64) -> Gate {
+    let m = m![Complex::one(),  Complex::zero();
+               Complex::zero(), Complex::new_euler(1f64, lambda)];
+
+    Gate::new(1, m)
+}
+
+/// The single qubit S (phase) gate, a `PI / 2` phase shift.
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Quantum_logic_gate#Phase_shift_gates)
+/// for more information.
+pub fn s() -> Gate {
+    phase(PI / 2f64)
+}
+
+/// The single qubit T gate, a `PI / 4` phase shift.
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Quantum_logic_gate#Phase_shift_gates)
+/// for more information.
+pub fn t() -> Gate {
+    phase(PI / 4f64)
+}
+
+/// The single qubit S-dagger gate, the inverse of `s` (a `-PI / 2` phase).
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Quantum_logic_gate#Phase_shift_gates)
+/// for more information.
+pub fn s_dagger() -> Gate {
+    phase(-PI / 2f64)
+}
+
+/// The single qubit T-dagger gate, the inverse of `t` (a `-PI / 4` phase).
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Quantum_logic_gate#Phase_shift_gates)
+/// for more information.
+pub fn t_dagger() -> Gate {
+    phase(-PI / 4f64)
+}
+
+/// The universal single qubit gate, parameterized by three Euler angles.
+///
+/// Every single qubit unitary can be written as `u3(theta, phi, lambda)`.
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Quantum_logic_gate#Universal_quantum_gates)
+/// for more information.
+pub fn u3(theta: f64, phi: f64, lambda: f64) -> Gate {
+    let c = (theta / 2f64).cos();
+    let s = (theta / 2f64).sin();
+
+    let m = m![c![c, 0f64],                  Complex::new_euler(-s, lambda);
+               Complex::new_euler(s, phi),   Complex::new_euler(c, phi + lambda)];
+
+    Gate::new(1, m)
+}
+
+/// The Quantum Fourier Transform on `width` qubits.
+///
+/// The entry at row `j`, column `k` is `(1 / sqrt(N)) * omega^{j*k}`, where
+/// `N = 2^width` and `omega = e^{2*PI*i/N}`.  This is a foundational subroutine
+/// for phase estimation and Shor-style algorithms.
+///
+/// This is an alias for `quantum_fourier_transform`, kept for symmetry with
+/// `qft_inverse`.
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Quantum_Fourier_transform)
+/// for more information.
+pub fn qft(width: usize) -> Gate {
+    quantum_fourier_transform(width)
+}
+
+/// The inverse Quantum Fourier Transform on `width` qubits.
+///
+/// This is the conjugate of `qft`, obtained by negating the phase angle.
+///
+/// See [Wikipedia](https://en.wikipedia.org/wiki/Quantum_Fourier_transform)
+/// for more information.
+pub fn qft_inverse(width: usize) -> Gate {
+    let n = Ket::size(width);
+    let scale = (n as f64).sqrt().recip();
+
+    let mut m = Matrix::new(n);
+
+    for j in 0..n {
+        for k in 0..n {
+            let angle = -2f64 * PI * (j * k) as f64 / n as f64;
+            m.set(j, k, c![scale * angle.cos(), scale * angle.sin()]);
+        }
+    }
+
+    Gate::new(width, m)
+}
+
 /// Convenience macro for testing a quantum gate.
 macro_rules! test_gate {
     ($computer:expr, $gate:expr, $from:expr, $to:expr) => {
@@ -349,6 +536,80 @@ fn phase_shift_test() {
     test_gate!(c, phase_shift(phi), 1, 1);
 }
 
+#[test]
+fn rx_test() {
+    use computer::QuantumComputer;
+
+    let mut c = QuantumComputer::new(1);
+
+    // A PI rotation about x flips the qubit (up to global phase).
+    test_gate!(c, rx(PI), 0, 1);
+    test_gate!(c, rx(PI), 1, 0);
+}
+
+#[test]
+fn ry_test() {
+    use computer::QuantumComputer;
+
+    let mut c = QuantumComputer::new(1);
+
+    // A PI rotation about y flips the qubit.
+    test_gate!(c, ry(PI), 0, 1);
+    test_gate!(c, ry(PI), 1, 0);
+}
+
+#[test]
+fn rz_test() {
+    use computer::QuantumComputer;
+
+    let theta = 0.7f64;
+    let mut c = QuantumComputer::new(1);
+
+    // A z rotation is diagonal and leaves the basis states in place.
+    test_gate!(c, rz(theta), 0, 0);
+    test_gate!(c, rz(theta), 1, 1);
+}
+
+#[test]
+fn phase_test() {
+    use computer::QuantumComputer;
+
+    let lambda = 0.3f64;
+    let mut c = QuantumComputer::new(1);
+
+    test_gate!(c, phase(lambda), 0, 0);
+    test_gate!(c, phase(lambda), 1, 1);
+}
+
+#[test]
+fn s_and_t_test() {
+    // S applies a PI/2 phase, so its |1> amplitude becomes i.
+    assert!(Complex::i().approx_eq(&s().matrix().get(1, 1)));
+
+    // T applies a PI/4 phase.
+    assert!(Complex::new_euler(1f64, PI / 4f64).approx_eq(&t().matrix().get(1, 1)));
+}
+
+#[test]
+fn s_and_t_dagger_test() {
+    // S-dagger applies the opposite PI/2 phase, i.e. -i on the |1> amplitude.
+    assert!(Complex::new_euler(1f64, -PI / 2f64).approx_eq(&s_dagger().matrix().get(1, 1)));
+
+    // T-dagger applies the opposite PI/4 phase.
+    assert!(Complex::new_euler(1f64, -PI / 4f64).approx_eq(&t_dagger().matrix().get(1, 1)));
+}
+
+#[test]
+fn u3_test() {
+    use computer::QuantumComputer;
+
+    let mut c = QuantumComputer::new(1);
+
+    // u3(PI, 0, 0) is a y rotation by PI and flips the qubit.
+    test_gate!(c, u3(PI, 0f64, 0f64), 0, 1);
+    test_gate!(c, u3(PI, 0f64, 0f64), 1, 0);
+}
+
 #[test]
 fn swap_test() {
     use computer::QuantumComputer;
@@ -401,6 +662,17 @@ fn controlled_test() {
     assert_eq!(controlled_not(), g);
 }
 
+#[test]
+fn multi_controlled_test() {
+    // A single control reproduces the ordinary controlled-NOT.
+    assert_eq!(controlled_not(),
+               multi_controlled(pauli_x().matrix(), &[0], 1, 2));
+
+    // Two controls and a Pauli-X target reproduce the Toffoli gate.
+    assert_eq!(toffoli(),
+               multi_controlled(pauli_x().matrix(), &[0, 1], 2, 3));
+}
+
 #[test]
 fn toffoli_test() {
     use computer::QuantumComputer;
@@ -454,6 +726,22 @@ fn quantum_fourier_transform_test() {
     assert!(c![0.0f64, 0.5f64].approx_eq(&qft.matrix().get(3, 3)));
 }
 
+#[test]
+fn qft_test() {
+    let q = qft(2);
+    let scale = 4f64.sqrt().recip();
+
+    // Applying the QFT to |00> yields the uniform superposition, i.e. column 0
+    // is `1 / sqrt(N)` throughout.
+    for j in 0..4 {
+        assert!(c![scale, 0f64].approx_eq(&q.matrix().get(j, 0)));
+    }
+
+    // The QFT is unitary: composing it with its inverse gives the identity.
+    let product = q.matrix() * qft_inverse(2).matrix();
+    assert!(Matrix::identity(4).approx_eq(&product));
+}
+
 #[test]
 fn permutation_test() {
     use computer::QuantumComputer;
@@ -4,7 +4,6 @@ use float_cmp::ApproxEqUlps;
 
 use complex::Complex;
 use gate::Gate;
-use matrix::MAX_SIZE;
 use registers::ClassicalRegister;
 
 /// A ket describes the state of a quantum register.
@@ -14,17 +13,17 @@ use registers::ClassicalRegister;
 /// of size _2^n_.  Theoretically, the sum of the square coefficient moduli
 /// must equal `1`.
 ///
-/// We store the elements (left-aligned) in an array of size `MAX_SIZE`, with
-/// the unused slots set to zero.
+/// We store the `size` elements in a heap-allocated `Vec`, so a ket's memory
+/// scales as `2^width` rather than being capped at a fixed dimension.
 ///
 /// See [Wikipedia](https://en.wikipedia.org/wiki/Bra%E2%80%93ket_notation) for
 /// more information.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Ket {
     size: usize,
 
     /// The ket's elements, w.r.t. the computational basis.
-    pub elements: [Complex; MAX_SIZE],
+    pub elements: Vec<Complex>,
 }
 
 impl Ket {
@@ -32,7 +31,7 @@ impl Ket {
     pub fn new(size: usize) -> Ket {
         Ket {
             size: size,
-            elements: [Complex::zero(); MAX_SIZE],
+            elements: vec![Complex::zero(); size],
         }
     }
 
@@ -48,6 +47,30 @@ impl Ket {
         ket
     }
 
+    /// Construct a ket directly from a vector of amplitudes.
+    ///
+    /// This loads an arbitrary superposition, removing the need to synthesize a
+    /// preparation circuit when a user just wants to start from a Bell state or
+    /// a custom distribution.
+    ///
+    /// # Panics
+    ///
+    /// We panic if the number of amplitudes is not a power of two, or if the
+    /// squared moduli do not sum to `1`.
+    pub fn from_amplitudes(amps: &[Complex]) -> Ket {
+        assert!(amps.len().is_power_of_two());
+
+        let mut ket = Ket::new(amps.len());
+
+        for (i, amp) in amps.iter().enumerate() {
+            ket.elements[i] = *amp;
+        }
+
+        assert!(ket.is_valid());
+
+        ket
+    }
+
     /// Is this structure a valid ket?
     #[allow(unused)]
     pub fn is_valid(&self) -> bool {
@@ -96,6 +119,28 @@ impl Ket {
     }
 }
 
+#[test]
+fn from_amplitudes_test() {
+    let sqrt2inv = 2.0f64.sqrt().recip();
+
+    // A Bell-like superposition over |00> and |11>.
+    let ket = Ket::from_amplitudes(&[c![sqrt2inv, 0.0],
+                                      c![0.0, 0.0],
+                                      c![0.0, 0.0],
+                                      c![sqrt2inv, 0.0]]);
+
+    assert!(ket.is_valid());
+    assert_eq!(c![sqrt2inv, 0.0], ket.elements[0]);
+    assert_eq!(c![sqrt2inv, 0.0], ket.elements[3]);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed")]
+fn bad_amplitudes_test() {
+    // Length three is not a power of two.
+    Ket::from_amplitudes(&[Complex::one(), Complex::zero(), Complex::zero()]);
+}
+
 #[test]
 fn valid_test() {
     let mut valid = Ket::new(3);
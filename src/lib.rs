@@ -12,6 +12,9 @@
 extern crate float_cmp;
 extern crate rand;
 
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
 pub mod complex;
 pub mod computer;
 pub mod gate;
@@ -19,4 +22,6 @@ pub mod gates;
 pub mod ket;
 pub mod matrix;
 pub mod other;
+pub mod qasm;
 pub mod registers;
+pub mod state_prep;
@@ -6,38 +6,28 @@ use std::ops::Mul;
 
 use complex::Complex;
 
-/// Max size of matrix and therefore ket.
-pub const MAX_SIZE: usize = 32;
+/// Heap-allocated vector of complex numbers, sized to match a ket.
+pub type Vector = Vec<Complex>;
 
-const MAX_ELEMENTS: usize = MAX_SIZE * MAX_SIZE;
-
-/// Efficient array of complex numbers.
-pub type Vector = [Complex; MAX_SIZE];
-
-/// Represents a square matrix over C of maximum size `MAX_SIZE`.
+/// Represents a square matrix over C.
 ///
-/// Each element is an instance of `Complex`, and we store the elements
-/// internally in an array of size `MAX_SIZE^2 * sizeof(Complex)`.
+/// Each element is an instance of `Complex`, and we store the `size * size`
+/// elements internally in a heap-allocated `Vec` in row-major order.
 ///
-/// In practice, this means each matrix occupies around `16KiB`.
-#[allow(missing_copy_implementations)]
+/// Allocating on demand means a matrix only occupies memory proportional to
+/// `size^2`, so a register's footprint scales as `2^width` and is bounded by
+/// available RAM rather than a fixed dimension ceiling.
 pub struct Matrix {
     size: usize,
-    elements: [Complex; MAX_ELEMENTS],
+    elements: Vec<Complex>,
 }
 
 impl Matrix {
     /// Construct a new zero-initialized matrix of given size.
-    ///
-    /// # Panics
-    ///
-    /// We panic if the given size exceeds `MAX_SIZE`.
     pub fn new(size: usize) -> Matrix {
-        assert!(size <= MAX_SIZE);
-
         Matrix {
             size: size,
-            elements: [Complex::zero(); MAX_ELEMENTS],
+            elements: vec![Complex::zero(); size * size],
         }
     }
 
@@ -45,9 +35,8 @@ impl Matrix {
     ///
     /// # Panics
     ///
-    /// We panic if the given size exceeds `MAX_SIZE`.
+    /// We panic if the number of elements is not `size * size`.
     pub fn new_from_elements(size: usize, elements: Vec<Complex>) -> Matrix {
-        assert!(size <= MAX_SIZE);
         assert!(size * size == elements.len());
 
         let mut m = Matrix::new(size);
@@ -60,23 +49,14 @@ impl Matrix {
     }
 
     /// Construct a new identity matrix of given size.
-    ///
-    /// # Panics
-    ///
-    /// We panic if the given size exceeds `MAX_SIZE`.
     pub fn identity(size: usize) -> Matrix {
-        assert!(size <= MAX_SIZE);
-
-        let mut elements = [Complex::zero(); MAX_ELEMENTS];
+        let mut m = Matrix::new(size);
 
         for i in 0..size {
-            elements[i * MAX_SIZE + i] = Complex::one();
+            m.set(i, i, Complex::one());
         }
 
-        Matrix {
-            size: size,
-            elements: elements,
-        }
+        m
     }
 
     /// Embed another matrix into this one, overrising elements.
@@ -98,6 +78,33 @@ impl Matrix {
         }
     }
 
+    /// Compute the Kronecker (tensor) product with another matrix.
+    ///
+    /// Given an `m x m` matrix `A` (`self`) and a `p x p` matrix `B` (`other`),
+    /// the result is an `(m*p) x (m*p)` matrix where
+    /// `result(i*p + r, j*p + s) = A(i, j) * B(r, s)`.
+    ///
+    /// This is the canonical way to lift a single-qubit gate acting on qubit
+    /// `k` into an operator on the whole register.
+    pub fn kronecker(&self, other: &Matrix) -> Matrix {
+        let size = self.size * other.size;
+
+        let mut m = Matrix::new(size);
+
+        for i in 0..self.size {
+            for j in 0..self.size {
+                for r in 0..other.size {
+                    for s in 0..other.size {
+                        let value = self.get(i, j) * other.get(r, s);
+                        m.set(i * other.size + r, j * other.size + s, value);
+                    }
+                }
+            }
+        }
+
+        m
+    }
+
     /// Permute the rows to generate a new matrix.
     ///
     /// Row _i_ goes to row _perutation[i]_.
@@ -162,12 +169,86 @@ impl Matrix {
 
     /// Get the element in position `(i, j)`.
     pub fn get(&self, i: usize, j: usize) -> Complex {
-        self.elements[i * MAX_SIZE + j]
+        self.elements[i * self.size + j]
     }
 
     /// Set the element in position `(i, j)` to `value`.
     pub fn set(&mut self, i: usize, j: usize, value: Complex) {
-        self.elements[i * MAX_SIZE + j] = value
+        self.elements[i * self.size + j] = value
+    }
+
+    /// The conjugate transpose (adjoint), i.e. `result(i, j) = conj(self(j, i))`.
+    ///
+    /// This is the adjoint needed for measurement operators and inverse gates.
+    pub fn dagger(&self) -> Matrix {
+        let mut m = Matrix::new(self.size);
+
+        for i in 0..self.size {
+            for j in 0..self.size {
+                m.set(i, j, self.get(j, i).conj());
+            }
+        }
+
+        m
+    }
+
+    /// Is this matrix unitary, i.e. is `self * self.dagger()` the identity?
+    ///
+    /// Every physically valid quantum gate must be unitary; this gives a cheap
+    /// validity check within the usual `approx_eq` tolerance.
+    pub fn is_unitary(&self) -> bool {
+        (self * &self.dagger()).approx_eq(&Matrix::identity(self.size))
+    }
+
+    /// The dot product of row `i` with `rhs`.
+    fn row_dot(&self, i: usize, rhs: &[Complex]) -> Complex {
+        let mut val = Complex::zero();
+
+        for k in 0..self.size {
+            val += self.get(i, k) * rhs[k];
+        }
+
+        val
+    }
+
+    /// Multiply this matrix by a vector, serially.
+    ///
+    /// Each output amplitude is an independent dot product of a matrix row with
+    /// `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// We panic if the vector's length does not match `self.size`.
+    #[cfg(not(feature = "parallel"))]
+    fn mul_vector(&self, rhs: &[Complex]) -> Vector {
+        assert_eq!(self.size, rhs.len());
+
+        let mut output = vec![Complex::zero(); self.size];
+
+        for i in 0..self.size {
+            output[i] = self.row_dot(i, rhs);
+        }
+
+        output
+    }
+
+    /// Multiply this matrix by a vector, in parallel.
+    ///
+    /// Because every output amplitude is an independent dot product over the
+    /// immutable row and `rhs`, we partition the output index range across
+    /// rayon's global thread pool (itself sized from the available CPUs).  This
+    /// is the bottleneck of `Gate` application past ~15 qubits.
+    ///
+    /// # Panics
+    ///
+    /// We panic if the vector's length does not match `self.size`.
+    #[cfg(feature = "parallel")]
+    fn mul_vector(&self, rhs: &[Complex]) -> Vector {
+        use rayon::prelude::*;
+
+        assert_eq!(self.size, rhs.len());
+
+        (0..self.size).into_par_iter().map(|i| self.row_dot(i, rhs)).collect()
     }
 
     /// Approximately equal test.
@@ -205,7 +286,7 @@ impl PartialEq for Matrix {
     fn eq(&self, other: &Matrix) -> bool {
         assert_eq!(self.size, other.size);
 
-        for i in 0..MAX_ELEMENTS {
+        for i in 0..self.size * self.size {
             if self.elements[i] != other.elements[i] {
                 return false;
             }
@@ -261,30 +342,12 @@ impl<'a> Mul<&'a Matrix> for &'a Matrix {
 ///
 /// # Panics
 ///
-/// We panic if the vector contains non-zero elements in
-/// positions `self.size` or beyond.
+/// We panic if the vector's length does not match `self.size`.
 impl<'a> Mul<&'a Vector> for &'a Matrix {
     type Output = Vector;
 
     fn mul(self, rhs: &Vector) -> Vector {
-        let mut output = [Complex::zero(); MAX_SIZE];
-
-        // Check that vector tail is zero
-        for i in self.size..MAX_SIZE {
-            assert_eq!(Complex::zero(), rhs[i])
-        }
-
-        for i in 0..self.size {
-            let mut val = Complex::zero();
-
-            for k in 0..self.size {
-                val += self.get(i, k) * rhs[k]
-            }
-
-            output[i] = val;
-        }
-
-        output
+        self.mul_vector(rhs)
     }
 }
 
@@ -292,11 +355,11 @@ impl<'a> Mul<&'a Vector> for &'a Matrix {
 fn matrix_test() {
     let m = m_real![1, 2; 3, 4];
 
-    let mut v: Vector = [Complex::zero(); MAX_SIZE];
+    let mut v: Vector = vec![Complex::zero(); 2];
     v[0] = c!(10f64, 0f64);
     v[1] = c!(20f64, 0f64);
 
-    let mut expected: Vector = [Complex::zero(); MAX_SIZE];
+    let mut expected: Vector = vec![Complex::zero(); 2];
     expected[0] = c!(50f64, 0f64);
     expected[1] = c!(110f64, 0f64);
 
@@ -319,6 +382,56 @@ fn embed_test() {
     assert_eq!(m_real![1, 2; 3, 5], m);
 }
 
+#[test]
+fn large_matrix_test() {
+    // The heap-backed representation lifts the old dimension-32 ceiling, so a
+    // width-6 register (size 64) is now representable.
+    let mut m = Matrix::identity(64);
+
+    m.set(63, 63, c!(2f64, 0f64));
+
+    assert_eq!(c!(2f64, 0f64), m.get(63, 63));
+    assert_eq!(Complex::one(), m.get(10, 10));
+}
+
+#[test]
+fn kronecker_test() {
+    let a = m_real![1, 2;
+                    3, 4];
+    let b = m_real![0, 1;
+                    1, 0];
+
+    let expected = m_real![0, 1, 0, 2;
+                           1, 0, 2, 0;
+                           0, 3, 0, 4;
+                           3, 0, 4, 0];
+
+    assert_eq!(expected, a.kronecker(&b));
+}
+
+#[test]
+fn dagger_test() {
+    let m = m![c![1f64, 2f64], c![3f64, 4f64];
+               c![5f64, 6f64], c![7f64, 8f64]];
+
+    let expected = m![c![1f64, -2f64], c![5f64, -6f64];
+                      c![3f64, -4f64], c![7f64, -8f64]];
+
+    assert_eq!(expected, m.dagger());
+}
+
+#[test]
+fn is_unitary_test() {
+    let sqrt2inv = 2.0f64.sqrt().recip();
+
+    // The Hadamard matrix is unitary; a non-unitary example is not.
+    let hadamard = m![c![sqrt2inv, 0f64], c![sqrt2inv, 0f64];
+                      c![sqrt2inv, 0f64], c![-sqrt2inv, 0f64]];
+
+    assert!(hadamard.is_unitary());
+    assert_eq!(false, m_real![1, 1; 0, 1].is_unitary());
+}
+
 #[test]
 fn permutation_test() {
     let m = m_real![1, 2; 3, 4];
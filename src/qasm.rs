@@ -0,0 +1,172 @@
+//! OpenQASM 2.0 import and export for `QuantumComputer` programs.
+//!
+//! This maps a useful subset of the OpenQASM 2.0 grammar onto the `gates`
+//! module, so circuits can be exchanged with other toolchains.  Named one- and
+//! two-qubit operations are expanded to the full register width via
+//! `Gate::on_qubits` before being applied.
+
+use gate::Gate;
+use gates;
+
+/// A single recorded instruction of a QASM program.
+///
+/// Qubit operands are register indices, matching the `q[i]` syntax.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instruction {
+    /// `h q[a];`
+    H(usize),
+    /// `x q[a];`
+    X(usize),
+    /// `y q[a];`
+    Y(usize),
+    /// `z q[a];`
+    Z(usize),
+    /// `cx q[a],q[b];`
+    Cx(usize, usize),
+    /// `ccx q[a],q[b],q[c];`
+    Ccx(usize, usize, usize),
+    /// `swap q[a],q[b];`
+    Swap(usize, usize),
+    /// `u1(lambda) q[a];`, mapped to a phase shift.
+    U1(f64, usize),
+    /// `measure q -> c;`
+    Measure,
+}
+
+/// Expand an instruction to a full register-width gate.
+///
+/// Returns `None` for `Measure`, which is a collapse rather than a gate.
+pub fn gate_for(inst: &Instruction, width: usize) -> Option<Gate> {
+    let gate = match *inst {
+        Instruction::H(a) => Gate::on_qubits(width, &[a], &gates::hadamard(1)),
+        Instruction::X(a) => Gate::on_qubits(width, &[a], &gates::pauli_x()),
+        Instruction::Y(a) => Gate::on_qubits(width, &[a], &gates::pauli_y()),
+        Instruction::Z(a) => Gate::on_qubits(width, &[a], &gates::pauli_z()),
+        Instruction::Cx(a, b) => Gate::on_qubits(width, &[a, b], &gates::controlled_not()),
+        Instruction::Ccx(a, b, c) => Gate::on_qubits(width, &[a, b, c], &gates::toffoli()),
+        Instruction::Swap(a, b) => Gate::on_qubits(width, &[a, b], &gates::swap()),
+        Instruction::U1(lambda, a) => Gate::on_qubits(width, &[a], &gates::phase_shift(lambda)),
+        Instruction::Measure => return None,
+    };
+
+    Some(gate)
+}
+
+/// Parse QASM source into a register width and a sequence of instructions.
+///
+/// We recognize the `OPENQASM 2.0;` header, `qreg`/`creg` declarations, the
+/// gate calls `h`, `x`, `y`, `z`, `cx`, `ccx`, `swap`, `u1`, and `measure`.
+/// `//` comment lines, `include` and `creg` statements are ignored; any other
+/// gate name panics.
+pub fn parse(src: &str) -> (usize, Vec<Instruction>) {
+    let mut width = 0;
+    let mut instructions = Vec::new();
+
+    for raw in src.split(';') {
+        // Drop `//` comment lines so they do not hide the statement after them.
+        let code: Vec<&str> = raw.lines().filter(|l| !l.trim().starts_with("//")).collect();
+        let code = code.join("\n");
+        let stmt = code.trim();
+
+        if stmt.is_empty() || stmt.starts_with("//") || stmt.starts_with("OPENQASM") ||
+           stmt.starts_with("include") || stmt.starts_with("creg") {
+            continue;
+        }
+
+        if stmt.starts_with("qreg") {
+            width = indices(stmt)[0];
+            continue;
+        }
+
+        if stmt.starts_with("measure") {
+            instructions.push(Instruction::Measure);
+            continue;
+        }
+
+        let mut parts = stmt.splitn(2, char::is_whitespace);
+        let head = parts.next().unwrap();
+        let operands = indices(parts.next().unwrap_or(""));
+
+        let inst = if head.starts_with("u1") {
+            Instruction::U1(param(head), operands[0])
+        } else {
+            match head {
+                "h" => Instruction::H(operands[0]),
+                "x" => Instruction::X(operands[0]),
+                "y" => Instruction::Y(operands[0]),
+                "z" => Instruction::Z(operands[0]),
+                "cx" => Instruction::Cx(operands[0], operands[1]),
+                "ccx" => Instruction::Ccx(operands[0], operands[1], operands[2]),
+                "swap" => Instruction::Swap(operands[0], operands[1]),
+                other => panic!("unsupported QASM instruction: {}", other),
+            }
+        };
+
+        instructions.push(inst);
+    }
+
+    (width, instructions)
+}
+
+/// Serialize a register width and instruction sequence back to QASM 2.0.
+pub fn emit(width: usize, instructions: &[Instruction]) -> String {
+    let mut out = String::new();
+
+    out.push_str("OPENQASM 2.0;\n");
+    out.push_str(&format!("qreg q[{}];\n", width));
+    out.push_str(&format!("creg c[{}];\n", width));
+
+    for inst in instructions {
+        let line = match *inst {
+            Instruction::H(a) => format!("h q[{}];\n", a),
+            Instruction::X(a) => format!("x q[{}];\n", a),
+            Instruction::Y(a) => format!("y q[{}];\n", a),
+            Instruction::Z(a) => format!("z q[{}];\n", a),
+            Instruction::Cx(a, b) => format!("cx q[{}],q[{}];\n", a, b),
+            Instruction::Ccx(a, b, c) => format!("ccx q[{}],q[{}],q[{}];\n", a, b, c),
+            Instruction::Swap(a, b) => format!("swap q[{}],q[{}];\n", a, b),
+            Instruction::U1(lambda, a) => format!("u1({}) q[{}];\n", lambda, a),
+            Instruction::Measure => "measure q -> c;\n".to_string(),
+        };
+
+        out.push_str(&line);
+    }
+
+    out
+}
+
+/// Extract every `[index]` in a fragment, e.g. `q[0],q[1]` yields `[0, 1]`.
+fn indices(s: &str) -> Vec<usize> {
+    s.split(',')
+        .filter_map(|token| {
+            let token = token.trim();
+            let start = token.find('[')?;
+            let end = token.find(']')?;
+            token[start + 1..end].trim().parse().ok()
+        })
+        .collect()
+}
+
+/// Extract the `(lambda)` parameter from a gate head such as `u1(0.3)`.
+fn param(head: &str) -> f64 {
+    let start = head.find('(').unwrap();
+    let end = head.find(')').unwrap();
+
+    head[start + 1..end].trim().parse().unwrap()
+}
+
+#[test]
+fn roundtrip_test() {
+    let src = "OPENQASM 2.0;\nqreg q[2];\ncreg c[2];\nx q[0];\ncx q[0],q[1];\n";
+
+    let (width, instructions) = parse(src);
+
+    assert_eq!(2, width);
+    assert_eq!(vec![Instruction::X(0), Instruction::Cx(0, 1)], instructions);
+
+    // Emitting and re-parsing yields the same program.
+    let (width2, instructions2) = parse(&emit(width, &instructions));
+
+    assert_eq!(width, width2);
+    assert_eq!(instructions, instructions2);
+}
@@ -2,10 +2,29 @@
 
 use rand;
 use std::cell::Cell;
+use std::collections::HashMap;
 
+use complex::Complex;
 use gate::Gate;
 use ket::Ket;
 
+/// The basis in which a single qubit is measured.
+///
+/// The computational (`Z`) basis is the usual `{|0>, |1>}`; the `X` and `Y`
+/// bases are reached by a basis-change rotation applied before measurement and
+/// undone afterwards.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MeasurementBasis {
+    /// The computational `{|0>, |1>}` basis.
+    Computational,
+
+    /// The `X` basis `{|+>, |->}`.
+    X,
+
+    /// The `Y` basis `{|+i>, |-i>}`.
+    Y,
+}
+
 /// Represents a register of an arbitrary number of qubits.
 ///
 /// The register consists `width` qubits, all of which are quantum
@@ -56,6 +75,26 @@ impl QuantumRegister {
         }
     }
 
+    /// Construct a new quantum register of given `width` from an arbitrary ket.
+    ///
+    /// This loads a prepared superposition directly, rather than starting from
+    /// a classical basis state as `new` does.
+    ///
+    /// # Panics
+    ///
+    /// We panic if the ket is not a valid state, or if its dimension does not
+    /// match `width` (i.e. is not `2^width`).
+    pub fn with_state(width: usize, ket: &Ket) -> QuantumRegister {
+        assert_eq!(Ket::size(width), ket.elements.len());
+        assert!(ket.is_valid());
+
+        QuantumRegister {
+            width: width,
+            collapsed: Cell::new(false),
+            ket: ket.clone(),
+        }
+    }
+
     /// Apply a quantum gate to this register, mutating its state.
     pub fn apply(&mut self, gate: Gate) {
         assert_eq!(false, self.collapsed.get());
@@ -72,12 +111,20 @@ impl QuantumRegister {
 
         self.collapsed = Cell::new(true);
 
-        // Algorithm:
-        // 1) we choose a random float between `0` and `1`
-        // 2) we partition `[0, 1 + epsilon)` using the ket coefficient square modulii
-        // 3) we randomly choose a coefficient
-        // 4) we return the matching state
+        ClassicalRegister::from_state(self.width, self.sample_state())
+    }
 
+    /// Draw a single classical state from the current ket distribution.
+    ///
+    /// Unlike `collapse` this does not mark the register collapsed, so it may
+    /// be called repeatedly against fresh random draws.
+    ///
+    /// Algorithm:
+    /// 1) we choose a random float between `0` and `1`
+    /// 2) we partition `[0, 1 + epsilon)` using the ket coefficient square modulii
+    /// 3) we randomly choose a coefficient
+    /// 4) we return the matching state
+    fn sample_state(&self) -> u32 {
         let sample = rand::random::<f64>() % 1.0;
         let mut cumulative = 0f64;
 
@@ -85,13 +132,234 @@ impl QuantumRegister {
             cumulative += coefficient.norm_sqr();
 
             if sample < cumulative {
-                return ClassicalRegister::from_state(self.width, state as u32);
+                return state as u32;
             }
         }
 
         // catch floating point imprecision
         // TODO log this somewhere
-        ClassicalRegister::from_state(self.width, 0)
+        0
+    }
+
+    /// Measure the register `shots` times without collapsing it.
+    ///
+    /// Where `collapse` yields a single classical state and invalidates the
+    /// register, this samples the current ket's probability distribution
+    /// `shots` times, leaving the superposition intact, and tallies how many
+    /// times each classical state is observed.  The returned map is keyed by
+    /// the measured state integer (see `ClassicalRegister::state`), the way a
+    /// real experiment records a histogram of outcomes.
+    pub fn measure_shots(&self, shots: usize) -> HashMap<u32, usize> {
+        assert_eq!(false, self.collapsed.get());
+
+        let mut counts = HashMap::new();
+
+        for _ in 0..shots {
+            *counts.entry(self.sample_state()).or_insert(0) += 1;
+        }
+
+        counts
+    }
+
+    /// Measure a single qubit in the computational basis, projecting the state.
+    ///
+    /// Unlike `collapse`, which decomposes the whole register at once, this
+    /// measures just qubit `index` and leaves the remaining qubits in a valid,
+    /// renormalized superposition so the register can keep evolving.
+    ///
+    /// Qubit `index` is addressed most-significant-bit first, matching the gate
+    /// machinery (`Gate::on_qubits`, `multi_controlled`, `collapse_in_basis`):
+    /// qubit `index` carries the ket bit `2^(width - 1 - index)`, so applying a
+    /// gate to "qubit 0" and then measuring qubit 0 hit the same physical qubit.
+    ///
+    /// Algorithm: sum the squared moduli of every amplitude whose `index`-th
+    /// bit is `0` to get `p0`, draw a uniform sample to pick the outcome, zero
+    /// out the amplitudes incompatible with it, then divide the survivors by
+    /// `sqrt(p)` to renormalize.
+    ///
+    /// # Panics
+    ///
+    /// We panic if the register is already collapsed or `index` is out of range.
+    pub fn measure_qubit(&mut self, index: usize) -> u8 {
+        assert_eq!(false, self.collapsed.get());
+        assert!(index < self.width);
+
+        let size = Ket::size(self.width);
+        let bit = 1usize << (self.width - 1 - index);
+
+        let mut p0 = 0f64;
+        for state in 0..size {
+            if 0 == state & bit {
+                p0 += self.ket.elements[state].norm_sqr();
+            }
+        }
+
+        let mut outcome: u8 = if rand::random::<f64>() % 1.0 < p0 { 0 } else { 1 };
+        let mut p = if 0 == outcome { p0 } else { 1f64 - p0 };
+
+        // If floating point error left the drawn outcome with ~0 probability,
+        // fall back to the other outcome to avoid dividing by zero.
+        if p < 1e-12 {
+            outcome = 1 - outcome;
+            p = 1f64 - p;
+        }
+
+        let scale = c![p.sqrt().recip(), 0f64];
+        for state in 0..size {
+            let bit_set = 0 != state & bit;
+
+            if (1 == outcome) == bit_set {
+                self.ket.elements[state] *= scale;
+            } else {
+                self.ket.elements[state] = Complex::zero();
+            }
+        }
+
+        outcome
+    }
+
+    /// Measure a single qubit in the given basis, projecting the state.
+    ///
+    /// For the `X` and `Y` bases we rotate the chosen eigenbasis onto the
+    /// computational basis (Hadamard for `X`, `S-dagger` then Hadamard for
+    /// `Y`), measure with `measure_qubit`, then apply the inverse rotation so
+    /// the surviving amplitudes are expressed back in the computational basis.
+    pub fn measure_qubit_in_basis(&mut self, index: usize, basis: MeasurementBasis) -> u8 {
+        let sqrt2inv = 2f64.sqrt().recip();
+        let hadamard = [[c![sqrt2inv, 0f64], c![sqrt2inv, 0f64]],
+                        [c![sqrt2inv, 0f64], c![-sqrt2inv, 0f64]]];
+        let s = [[Complex::one(), Complex::zero()],
+                 [Complex::zero(), Complex::i()]];
+        let s_dagger = [[Complex::one(), Complex::zero()],
+                        [Complex::zero(), -Complex::i()]];
+
+        match basis {
+            MeasurementBasis::Computational => self.measure_qubit(index),
+            MeasurementBasis::X => {
+                self.apply_single_qubit(index, &hadamard);
+                let outcome = self.measure_qubit(index);
+                self.apply_single_qubit(index, &hadamard);
+                outcome
+            }
+            MeasurementBasis::Y => {
+                self.apply_single_qubit(index, &s_dagger);
+                self.apply_single_qubit(index, &hadamard);
+                let outcome = self.measure_qubit(index);
+                self.apply_single_qubit(index, &hadamard);
+                self.apply_single_qubit(index, &s);
+                outcome
+            }
+        }
+    }
+
+    /// Apply a `2x2` unitary to a single qubit, mutating the ket in place.
+    ///
+    /// This acts only on qubit `index` (most-significant-bit first, bit value
+    /// `2^(width - 1 - index)`), pairing up the amplitudes that differ in that
+    /// bit and mixing them by `u`.
+    fn apply_single_qubit(&mut self, index: usize, u: &[[Complex; 2]; 2]) {
+        let size = Ket::size(self.width);
+        let bit = 1usize << (self.width - 1 - index);
+
+        for state in 0..size {
+            if 0 == state & bit {
+                let a0 = self.ket.elements[state];
+                let a1 = self.ket.elements[state | bit];
+
+                self.ket.elements[state] = u[0][0] * a0 + u[0][1] * a1;
+                self.ket.elements[state | bit] = u[1][0] * a0 + u[1][1] * a1;
+            }
+        }
+    }
+
+    /// Measure the register `shots` times, pairing each outcome with its count.
+    ///
+    /// This is a convenience wrapper around `measure_shots` which expands every
+    /// sampled state integer back into a `ClassicalRegister`.
+    pub fn measure_shots_classical(&self, shots: usize) -> Vec<(ClassicalRegister, usize)> {
+        self.measure_shots(shots)
+            .into_iter()
+            .map(|(state, count)| (ClassicalRegister::from_state(self.width, state), count))
+            .collect()
+    }
+
+    /// Render the current ket as a LaTeX `bmatrix` column vector.
+    ///
+    /// Following Q#'s `DumpMachine`, this lets users inspect and publish the
+    /// exact amplitudes of an intermediate state.  For terminals without LaTeX,
+    /// `dump_state_plain` gives an equivalent plain-text rendering.
+    pub fn dump_state(&self) -> String {
+        let size = Ket::size(self.width);
+
+        let rows: Vec<String> = (0..size)
+            .map(|state| {
+                let z = self.ket.elements[state];
+                format!("{:.3}{:+.3}i", z.re(), z.im())
+            })
+            .collect();
+
+        format!("\\begin{{bmatrix}}\n{}\n\\end{{bmatrix}}", rows.join(" \\\\\n"))
+    }
+
+    /// Render the current ket as a plain-text column vector, one amplitude per
+    /// line.
+    ///
+    /// This is the fallback for terminals without LaTeX, mirroring the rows of
+    /// `dump_state` without the surrounding `bmatrix` markup.
+    pub fn dump_state_plain(&self) -> String {
+        let size = Ket::size(self.width);
+
+        (0..size)
+            .map(|state| {
+                let z = self.ket.elements[state];
+                format!("{:.3}{:+.3}i", z.re(), z.im())
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Sample classical outcomes from the current ket without collapsing.
+    ///
+    /// This shares `collapse`'s "partition `[0, 1)` by square moduli and pick a
+    /// bucket" semantics, but leaves the register intact and returns a count of
+    /// how many times each classical state was observed across `shots` draws.
+    ///
+    /// We precompute the cumulative distribution once, so each of the `shots`
+    /// draws is `O(log N)` via binary search rather than `O(N)`.
+    pub fn sample(&self, shots: usize) -> HashMap<u32, usize> {
+        use std::cmp::Ordering;
+
+        assert_eq!(false, self.collapsed.get());
+
+        let size = Ket::size(self.width);
+
+        let mut cumulative = Vec::with_capacity(size);
+        let mut running = 0f64;
+        for state in 0..size {
+            running += self.ket.elements[state].norm_sqr();
+            cumulative.push(running);
+        }
+
+        let mut counts = HashMap::new();
+
+        for _ in 0..shots {
+            let sample = rand::random::<f64>() % 1.0;
+
+            // The bucket is the first cumulative bound strictly above `sample`.
+            let state = match cumulative.binary_search_by(|c| if *c <= sample {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }) {
+                Ok(state) => state,
+                Err(state) => state,
+            };
+            let state = state.min(size - 1);
+
+            *counts.entry(state as u32).or_insert(0) += 1;
+        }
+
+        counts
     }
 
     /// Compute the probabilities of each state without collapsing.
@@ -161,6 +429,99 @@ fn probabilities_test() {
     assert!(0.5f64.approx_eq_ulps(&r.probabilities()[1], 10));
 }
 
+#[test]
+fn measure_shots_test() {
+    use gates;
+
+    // A Hadamard on a single zeroed qubit gives an even split over |0> and |1>.
+    let nibble = ClassicalRegister::zeroed(1);
+    let mut r: QuantumRegister = QuantumRegister::new(1, &nibble);
+    r.apply(gates::hadamard(1));
+
+    let counts = r.measure_shots(1000);
+
+    // Every shot lands on a valid state and the register is left intact.
+    let total: usize = counts.values().sum();
+    assert_eq!(1000, total);
+    assert!(counts.keys().all(|&state| state < 2));
+    assert_eq!(2, r.probabilities().len());
+}
+
+#[test]
+fn sample_test() {
+    use gates;
+
+    let nibble = ClassicalRegister::zeroed(1);
+    let mut r: QuantumRegister = QuantumRegister::new(1, &nibble);
+    r.apply(gates::hadamard(1));
+
+    let counts = r.sample(1000);
+
+    let total: usize = counts.values().sum();
+    assert_eq!(1000, total);
+    assert!(counts.keys().all(|&state| state < 2));
+}
+
+#[test]
+fn with_state_test() {
+    use float_cmp::ApproxEqUlps;
+
+    // Load an even superposition directly and check the probabilities.
+    let sqrt2inv = 2.0f64.sqrt().recip();
+    let ket = Ket::from_amplitudes(&[c![sqrt2inv, 0.0], c![sqrt2inv, 0.0]]);
+    let r = QuantumRegister::with_state(1, &ket);
+
+    assert_eq!(2, r.probabilities().len());
+    assert!(0.5f64.approx_eq_ulps(&r.probabilities()[0], 10));
+    assert!(0.5f64.approx_eq_ulps(&r.probabilities()[1], 10));
+}
+
+#[test]
+#[should_panic]
+fn with_state_width_mismatch_test() {
+    // A two-element ket is not a valid state for a width-2 register.
+    let sqrt2inv = 2.0f64.sqrt().recip();
+    let ket = Ket::from_amplitudes(&[c![sqrt2inv, 0.0], c![sqrt2inv, 0.0]]);
+
+    QuantumRegister::with_state(2, &ket);
+}
+
+#[test]
+fn dump_state_test() {
+    // |0> dumps as a column vector starting with amplitude 1.
+    let zero = ClassicalRegister::zeroed(1);
+    let r: QuantumRegister = QuantumRegister::new(1, &zero);
+
+    let latex = r.dump_state();
+
+    assert!(latex.starts_with("\\begin{bmatrix}"));
+    assert!(latex.contains("1.000+0.000i"));
+}
+
+#[test]
+fn measure_qubit_test() {
+    // |10>: with qubit 0 the most significant bit, qubit 0 is set and qubit 1
+    // is clear.
+    let two = ClassicalRegister::from_state(2, 2);
+    let mut r: QuantumRegister = QuantumRegister::new(2, &two);
+
+    assert_eq!(1, r.measure_qubit(0));
+    assert_eq!(0, r.measure_qubit(1));
+}
+
+#[test]
+fn measure_qubit_in_basis_test() {
+    use gates;
+
+    // A Hadamard on |0> prepares |+>, an X eigenstate, so an X-basis
+    // measurement is deterministic.
+    let zero = ClassicalRegister::zeroed(1);
+    let mut r: QuantumRegister = QuantumRegister::new(1, &zero);
+    r.apply(gates::hadamard(1));
+
+    assert_eq!(0, r.measure_qubit_in_basis(0, MeasurementBasis::X));
+}
+
 /// Represents a non-quantum register of `width()` bits.
 ///
 /// We store this inefficiently for clarity.